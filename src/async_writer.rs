@@ -0,0 +1,237 @@
+use crate::writer::{NormalizedTimeTag, time_tag_schema};
+use crate::writer_config::WriterConfig;
+use anyhow::{Result, bail};
+use arrow::array::{UInt16Array, UInt16Builder, UInt64Array, UInt64Builder};
+use arrow::record_batch::RecordBatch;
+use chrono::Utc;
+use parquet::arrow::async_writer::AsyncArrowWriter;
+use parquet::file::properties::WriterProperties;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs::File;
+
+const DEFAULT_MAX_CHUNK_ROWS: usize = 20_000_000;
+const DEFAULT_MAX_FILE_ROWS: usize = 200_000_000;
+
+/// Async counterpart of [`crate::writer::ChunkedFileWriter`], built on
+/// `parquet::arrow::AsyncArrowWriter` so encoding can overlap with I/O
+/// against non-blocking destinations (sockets, cloud object stores) instead
+/// of the synchronous `File`-backed `ArrowWriter`. `AsyncArrowWriter` buffers
+/// the in-progress row group in memory until `max_row_group_size` rows
+/// accumulate; `write_buffer_size` (see [`WriterConfig`]) triggers an earlier
+/// flush once that buffer grows past a byte threshold, bounding peak memory.
+struct AsyncChunkedFileWriter {
+    output_dir: PathBuf,
+    name: String,
+    file_timestamp: String,
+    schema: Arc<arrow::datatypes::Schema>,
+    properties: WriterProperties,
+    max_chunk_rows: usize,
+    max_chunk_count: usize,
+    write_buffer_size: usize,
+    arrow_writer: Option<AsyncArrowWriter<File>>,
+    channel_array_builder: UInt16Builder,
+    time_tag_array_builder: UInt64Builder,
+    array_length: usize,
+    chunk_count: usize,
+    total_files: usize,
+}
+
+impl AsyncChunkedFileWriter {
+    async fn new(
+        output_dir: &Path,
+        name: &str,
+        max_chunk_rows: usize,
+        max_file_rows: usize,
+        config: &WriterConfig,
+    ) -> Result<Self> {
+        if !output_dir.is_dir() {
+            bail!(
+                "Requested output path {} is not a directory.",
+                output_dir.display()
+            );
+        }
+        let schema = time_tag_schema();
+        let properties = config.build_properties();
+        let max_chunk_count = max_file_rows / max_chunk_rows;
+        let file_timestamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let total_files = 1;
+        let initial_file = File::create(
+            output_dir.join(format!("{file_timestamp}_{name}_{total_files:0>4}.parquet")),
+        )
+        .await?;
+        let arrow_writer =
+            AsyncArrowWriter::try_new(initial_file, schema.clone(), Some(properties.clone()))?;
+
+        Ok(AsyncChunkedFileWriter {
+            output_dir: output_dir.to_path_buf(),
+            name: name.to_string(),
+            file_timestamp,
+            schema,
+            properties,
+            max_chunk_rows,
+            max_chunk_count,
+            write_buffer_size: config.write_buffer_size,
+            arrow_writer: Some(arrow_writer),
+            channel_array_builder: UInt16Array::builder(max_chunk_rows),
+            time_tag_array_builder: UInt64Array::builder(max_chunk_rows),
+            array_length: 0,
+            chunk_count: 0,
+            total_files,
+        })
+    }
+
+    async fn push(&mut self, event: NormalizedTimeTag) -> Result<()> {
+        self.array_length += 1;
+        self.channel_array_builder.append_value(event.channel_id);
+        self.time_tag_array_builder.append_value(event.time_tag_ps);
+
+        if self.array_length >= self.max_chunk_rows {
+            self.flush_chunk().await?;
+        }
+
+        if self.chunk_count > self.max_chunk_count {
+            self.rotate_file().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn flush_chunk(&mut self) -> Result<()> {
+        let batch = RecordBatch::try_new(
+            self.schema.clone(),
+            vec![
+                Arc::new(self.channel_array_builder.finish()),
+                Arc::new(self.time_tag_array_builder.finish()),
+            ],
+        )?;
+        let arrow_writer = self.arrow_writer.as_mut().expect("writer rotated away");
+        arrow_writer.write(&batch).await?;
+        if arrow_writer.in_progress_size() > self.write_buffer_size {
+            arrow_writer.flush().await?;
+        }
+        self.array_length = 0;
+        self.chunk_count += 1;
+        Ok(())
+    }
+
+    async fn rotate_file(&mut self) -> Result<()> {
+        // close() flushes any buffered data and writes the footer for the
+        // file being closed before we move on to the next one.
+        self.arrow_writer
+            .take()
+            .expect("writer rotated away")
+            .close()
+            .await?;
+        self.chunk_count = 0;
+        self.total_files += 1;
+
+        let new_file = File::create(self.output_dir.join(format!(
+            "{}_{}_{:0>4}.parquet",
+            self.file_timestamp, self.name, self.total_files
+        )))
+        .await?;
+        self.arrow_writer = Some(AsyncArrowWriter::try_new(
+            new_file,
+            self.schema.clone(),
+            Some(self.properties.clone()),
+        )?);
+        Ok(())
+    }
+
+    async fn finish(mut self) -> Result<()> {
+        if self.array_length > 0 {
+            self.flush_chunk().await?;
+        }
+        self.arrow_writer
+            .take()
+            .expect("writer rotated away")
+            .close()
+            .await?;
+        Ok(())
+    }
+}
+
+/// Async variant of [`crate::writer::write_time_tags`] that drives
+/// `AsyncArrowWriter` over `tokio::fs::File`, overlapping batch encoding with
+/// non-blocking I/O instead of blocking on the synchronous `ArrowWriter`.
+pub async fn write_time_tags_async(
+    time_tags: Vec<NormalizedTimeTag>,
+    output_dir: &Path,
+    name: &str,
+    config: &WriterConfig,
+) -> Result<()> {
+    let mut writer = AsyncChunkedFileWriter::new(
+        output_dir,
+        name,
+        DEFAULT_MAX_CHUNK_ROWS,
+        DEFAULT_MAX_FILE_ROWS,
+        config,
+    )
+    .await?;
+    for event in time_tags {
+        writer.push(event).await?;
+    }
+    writer.finish().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    #[tokio::test]
+    async fn write_time_tags_async_round_trips_all_rows() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let time_tags = vec![
+            NormalizedTimeTag { channel_id: 0, time_tag_ps: 100 },
+            NormalizedTimeTag { channel_id: 1, time_tag_ps: 200 },
+            NormalizedTimeTag { channel_id: 0, time_tag_ps: 300 },
+        ];
+
+        write_time_tags_async(time_tags, output_dir.path(), "test", &WriterConfig::default())
+            .await
+            .unwrap();
+
+        let mut total_rows = 0;
+        for entry in std::fs::read_dir(output_dir.path()).unwrap() {
+            let path = entry.unwrap().path();
+            let file = std::fs::File::open(&path).unwrap();
+            let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+            total_rows += builder.metadata().file_metadata().num_rows() as usize;
+        }
+        assert_eq!(total_rows, 3);
+    }
+
+    #[tokio::test]
+    async fn write_buffer_size_triggers_an_early_flush() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let config = WriterConfig {
+            write_buffer_size: 1,
+            ..WriterConfig::default()
+        };
+        let mut writer = AsyncChunkedFileWriter::new(output_dir.path(), "test", 100, 100_000, &config)
+            .await
+            .unwrap();
+        for i in 0..1000u64 {
+            writer
+                .push(NormalizedTimeTag { channel_id: (i % 2) as u16, time_tag_ps: i })
+                .await
+                .unwrap();
+        }
+        writer.finish().await.unwrap();
+
+        let path = std::fs::read_dir(output_dir.path())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        let file = std::fs::File::open(&path).unwrap();
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+        assert!(
+            builder.metadata().row_groups().len() > 1,
+            "a 1-byte write_buffer_size should have forced multiple row group flushes"
+        );
+    }
+}
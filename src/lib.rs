@@ -0,0 +1,6 @@
+pub mod async_writer;
+pub mod metadata;
+pub mod parallel;
+pub mod query;
+pub mod writer;
+pub mod writer_config;
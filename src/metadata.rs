@@ -0,0 +1,160 @@
+use crate::writer::{CHANNEL_COLUMN_NAME, TIME_TAG_COLUMN_NAME};
+use anyhow::{Context, Result};
+use arrow::array::{Array, UInt16Array, UInt64Array};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::arrow_reader::statistics::StatisticsConverter;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+/// Print per-file metadata for every `*.parquet` file produced by
+/// [`crate::writer::write_time_tags`]: row group count, total rows,
+/// compressed/uncompressed sizes, the declared schema, and the min/max
+/// `time_tag` and `channel` values per row group -- all read from the footer
+/// statistics, without scanning any row data. This gives a quick way to
+/// validate that file rotation, chunk sizing, and sorting behaved as
+/// expected across a large batch of generated files, and to spot files that
+/// violated the intended ~2 GiB / 200M-row targets.
+///
+/// `dir_or_glob` is either a directory (every `*.parquet` file directly
+/// inside it is printed) or a glob pattern such as `"out/*.parquet"` or
+/// `"out/**/*_simulation-1_*.parquet"` (file names start with the write
+/// timestamp, so a name-based pattern needs a leading `*`).
+pub fn print_parquet_metadata(dir_or_glob: &str) -> Result<()> {
+    let paths = resolve_paths(dir_or_glob)?;
+
+    for path in &paths {
+        print_file_metadata(path)
+            .with_context(|| format!("failed to read metadata for {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Resolve `dir_or_glob` to the sorted `*.parquet` files it names: every
+/// `*.parquet` file directly inside it when it's a directory, or every match
+/// when it's a glob pattern.
+fn resolve_paths(dir_or_glob: &str) -> Result<Vec<PathBuf>> {
+    let mut paths: Vec<PathBuf> = if Path::new(dir_or_glob).is_dir() {
+        std::fs::read_dir(dir_or_glob)
+            .with_context(|| format!("failed to read directory {dir_or_glob}"))?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "parquet"))
+            .collect()
+    } else {
+        glob::glob(dir_or_glob)
+            .with_context(|| format!("invalid glob pattern {dir_or_glob}"))?
+            .filter_map(|entry| entry.ok())
+            .collect()
+    };
+    paths.sort();
+    Ok(paths)
+}
+
+fn print_file_metadata(path: &Path) -> Result<()> {
+    let file = File::open(path)?;
+    let builder = ParquetRecordBatchReaderBuilder::try_new(file)?;
+    let metadata = builder.metadata();
+    let arrow_schema = builder.schema();
+    let row_groups = metadata.row_groups();
+
+    let total_rows = metadata.file_metadata().num_rows();
+    let compressed_size: i64 = row_groups.iter().map(|rg| rg.compressed_size()).sum();
+    let uncompressed_size: i64 = row_groups.iter().map(|rg| rg.total_byte_size()).sum();
+
+    println!("{}", path.display());
+    println!("  schema: {arrow_schema:?}");
+    println!("  row groups: {}", row_groups.len());
+    println!("  total rows: {total_rows}");
+    println!("  compressed size: {compressed_size} bytes");
+    println!("  uncompressed size: {uncompressed_size} bytes");
+
+    let time_tag_converter =
+        StatisticsConverter::try_new(TIME_TAG_COLUMN_NAME, arrow_schema, builder.parquet_schema())?;
+    let channel_converter =
+        StatisticsConverter::try_new(CHANNEL_COLUMN_NAME, arrow_schema, builder.parquet_schema())?;
+
+    let time_tag_mins = time_tag_converter.row_group_mins(row_groups.iter())?;
+    let time_tag_maxes = time_tag_converter.row_group_maxes(row_groups.iter())?;
+    let channel_mins = channel_converter.row_group_mins(row_groups.iter())?;
+    let channel_maxes = channel_converter.row_group_maxes(row_groups.iter())?;
+
+    let time_tag_mins = time_tag_mins
+        .as_any()
+        .downcast_ref::<UInt64Array>()
+        .expect("time_tag column is UInt64");
+    let time_tag_maxes = time_tag_maxes
+        .as_any()
+        .downcast_ref::<UInt64Array>()
+        .expect("time_tag column is UInt64");
+    let channel_mins = channel_mins
+        .as_any()
+        .downcast_ref::<UInt16Array>()
+        .expect("channel column is UInt16");
+    let channel_maxes = channel_maxes
+        .as_any()
+        .downcast_ref::<UInt16Array>()
+        .expect("channel column is UInt16");
+
+    for (row_group_idx, row_group) in row_groups.iter().enumerate() {
+        println!(
+            "  row group {row_group_idx}: rows={}, time_tag=[{}, {}], channel=[{}, {}]",
+            row_group.num_rows(),
+            time_tag_mins.value(row_group_idx),
+            time_tag_maxes.value(row_group_idx),
+            channel_mins.value(row_group_idx),
+            channel_maxes.value(row_group_idx),
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer::{NormalizedTimeTag, write_time_tags};
+    use crate::writer_config::WriterConfig;
+
+    #[test]
+    fn resolve_paths_matches_a_directory_and_an_equivalent_glob() {
+        let output_dir = tempfile::tempdir().unwrap();
+        write_time_tags(
+            vec![NormalizedTimeTag { channel_id: 0, time_tag_ps: 100 }],
+            output_dir.path(),
+            "a",
+            &WriterConfig::default(),
+        )
+        .unwrap();
+        write_time_tags(
+            vec![NormalizedTimeTag { channel_id: 1, time_tag_ps: 200 }],
+            output_dir.path(),
+            "b",
+            &WriterConfig::default(),
+        )
+        .unwrap();
+
+        let dir_paths = resolve_paths(output_dir.path().to_str().unwrap()).unwrap();
+        assert_eq!(dir_paths.len(), 2);
+
+        let glob_pattern = output_dir.path().join("*.parquet");
+        let glob_paths = resolve_paths(glob_pattern.to_str().unwrap()).unwrap();
+        assert_eq!(glob_paths, dir_paths);
+    }
+
+    #[test]
+    fn print_parquet_metadata_succeeds_against_a_directory_of_written_files() {
+        let output_dir = tempfile::tempdir().unwrap();
+        write_time_tags(
+            vec![
+                NormalizedTimeTag { channel_id: 0, time_tag_ps: 100 },
+                NormalizedTimeTag { channel_id: 1, time_tag_ps: 200 },
+            ],
+            output_dir.path(),
+            "test",
+            &WriterConfig::default(),
+        )
+        .unwrap();
+
+        print_parquet_metadata(output_dir.path().to_str().unwrap()).unwrap();
+    }
+}
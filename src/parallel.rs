@@ -0,0 +1,141 @@
+use crate::writer::{NormalizedTimeTag, write_time_tags};
+use crate::writer_config::WriterConfig;
+use anyhow::Result;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::ops::Range;
+use std::path::Path;
+use std::thread;
+
+/// Split `simulation_range` into `partition_count` contiguous, equal-width
+/// picosecond sub-ranges and hand each to `generate_partition` on its own
+/// thread with an independently seeded `StdRng` (seeded from `seed` plus the
+/// partition index, so the whole run stays reproducible). Each partition is
+/// sorted and written to its own `*_NNNN.parquet` file set under a
+/// deterministic, per-partition name, all in parallel -- removing the
+/// single-threaded `sort_by_key` bottleneck of generating and sorting the
+/// entire dataset on one thread. Because the partitions are disjoint,
+/// contiguous time windows and are named in partition order, the combined
+/// output across all partitions' files remains globally time-sorted: tags
+/// `generate_partition` returns outside its given range are dropped rather
+/// than trusted, since a caller could otherwise let a single straggler row
+/// (e.g. `tag0 + jitter` landing just past `partition_end`) break that
+/// guarantee for every downstream reader.
+pub fn generate_and_write_time_tags_parallel<F>(
+    simulation_range: Range<u64>,
+    partition_count: usize,
+    seed: u64,
+    generate_partition: F,
+    output_dir: &Path,
+    name: &str,
+    config: &WriterConfig,
+) -> Result<()>
+where
+    F: Fn(Range<u64>, &mut StdRng) -> Vec<NormalizedTimeTag> + Sync,
+{
+    let span = simulation_range.end - simulation_range.start;
+    let partition_width = span / partition_count as u64;
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..partition_count)
+            .map(|partition_idx| {
+                let partition_start = simulation_range.start + partition_width * partition_idx as u64;
+                let partition_end = if partition_idx + 1 == partition_count {
+                    simulation_range.end
+                } else {
+                    partition_start + partition_width
+                };
+                let generate_partition = &generate_partition;
+                scope.spawn(move || -> Result<()> {
+                    let mut rng = StdRng::seed_from_u64(seed.wrapping_add(partition_idx as u64));
+                    let mut time_tags = generate_partition(partition_start..partition_end, &mut rng);
+                    time_tags.retain(|tag| (partition_start..partition_end).contains(&tag.time_tag_ps));
+                    time_tags.sort_by_key(|tag| tag.time_tag_ps);
+                    write_time_tags(
+                        time_tags,
+                        output_dir,
+                        &format!("{name}_{partition_idx:0>4}"),
+                        config,
+                    )
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().expect("partition generation thread panicked")?;
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer::TIME_TAG_COLUMN_NAME;
+    use arrow::array::UInt64Array;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use parquet::arrow::arrow_reader::statistics::StatisticsConverter;
+
+    #[test]
+    fn partitions_are_written_one_file_per_partition_and_stay_time_sorted_across_files() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let partition_count = 4;
+
+        // Mirrors main.rs's jitter-past-partition_end shape, so the retain()
+        // filter in generate_and_write_time_tags_parallel is actually exercised.
+        let generate_partition = |partition_range: Range<u64>, _rng: &mut StdRng| {
+            vec![
+                NormalizedTimeTag { channel_id: 0, time_tag_ps: partition_range.start },
+                NormalizedTimeTag { channel_id: 1, time_tag_ps: partition_range.end - 1 },
+                NormalizedTimeTag { channel_id: 1, time_tag_ps: partition_range.end + 10 },
+            ]
+        };
+
+        generate_and_write_time_tags_parallel(
+            0..400,
+            partition_count,
+            42,
+            generate_partition,
+            output_dir.path(),
+            "test",
+            &WriterConfig::default(),
+        )
+        .unwrap();
+
+        let mut paths: Vec<_> = std::fs::read_dir(output_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        paths.sort();
+        assert_eq!(paths.len(), partition_count, "expected one file per partition");
+        for (partition_idx, path) in paths.iter().enumerate() {
+            assert!(
+                path.file_name().unwrap().to_str().unwrap().contains(&format!("test_{partition_idx:0>4}")),
+                "file {} should be named for partition {partition_idx}",
+                path.display()
+            );
+        }
+
+        let mut previous_max = None;
+        for (partition_idx, path) in paths.iter().enumerate() {
+            let partition_end = 100 * (partition_idx as u64 + 1);
+            let file = std::fs::File::open(path).unwrap();
+            let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+            let metadata = builder.metadata();
+            let arrow_schema = builder.schema();
+            let row_groups = metadata.row_groups();
+            let converter =
+                StatisticsConverter::try_new(TIME_TAG_COLUMN_NAME, arrow_schema, builder.parquet_schema())
+                    .unwrap();
+            let mins = converter.row_group_mins(row_groups.iter()).unwrap();
+            let maxes = converter.row_group_maxes(row_groups.iter()).unwrap();
+            let min = mins.as_any().downcast_ref::<UInt64Array>().unwrap().value(0);
+            let max = maxes.as_any().downcast_ref::<UInt64Array>().unwrap().value(0);
+            assert!(max < partition_end, "every partition's jittered row should have been dropped");
+            if let Some(previous_max) = previous_max {
+                assert!(previous_max <= min, "partitions must stay time-sorted across files");
+            }
+            previous_max = Some(max);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::writer::{CHANNEL_COLUMN_NAME, TIME_TAG_COLUMN_IDX};
+use parquet::basic::Compression;
+use parquet::file::properties::{EnabledStatistics, WriterProperties};
+use parquet::format::SortingColumn;
+use parquet::schema::types::ColumnPath;
+
+/// Default `data_page_row_count_limit`, matching the parquet crate's own
+/// default. Exposed here so callers can see what they get when they don't
+/// override it.
+pub const DEFAULT_DATA_PAGE_ROW_COUNT_LIMIT: usize = 20_000;
+
+/// Default `write_buffer_size`, matching the 1,000,000-byte early-flush threshold
+/// `AsyncArrowWriter`'s own docs use as an example.
+pub const DEFAULT_WRITE_BUFFER_SIZE: usize = 1_000_000;
+
+/// Writer-level knobs threaded into `ArrowWriter`/`AsyncArrowWriter` via
+/// `parquet::file::properties::WriterProperties`, shared by every writer in
+/// this crate so a given file's compression, sorting metadata, and bloom
+/// filter settings don't drift between the sync and async write paths.
+pub struct WriterConfig {
+    /// Compression codec applied to every column (e.g. `Compression::ZSTD(..)`
+    /// or `Compression::SNAPPY`).
+    pub compression: Compression,
+
+    /// When `Some`, enables a bloom filter on the `channel` column so readers
+    /// can skip row groups that don't contain a queried channel; `None`
+    /// writes no bloom filter. The inner value is the expected number of
+    /// distinct values (NDV); `Some(None)` falls back to the writer's default
+    /// NDV.
+    pub channel_bloom_filter_ndv: Option<Option<u64>>,
+
+    /// Row-count boundary for the page index on the (time-sorted) `time_tag`
+    /// column: a new data page starts once the current one holds this many
+    /// rows. Smaller values produce finer-grained page boundaries, letting
+    /// [`crate::query::query_time_range`] prune more precisely at the cost of
+    /// more page index metadata.
+    pub data_page_row_count_limit: usize,
+
+    /// Only consulted by [`crate::async_writer::write_time_tags_async`]: once
+    /// `AsyncArrowWriter::in_progress_size` exceeds this many bytes, the
+    /// in-progress row group is flushed early instead of waiting for
+    /// `max_row_group_size` rows to accumulate, bounding peak memory when
+    /// writing from a live, unbounded event source.
+    pub write_buffer_size: usize,
+}
+
+impl Default for WriterConfig {
+    fn default() -> Self {
+        WriterConfig {
+            compression: Compression::UNCOMPRESSED,
+            channel_bloom_filter_ndv: None,
+            data_page_row_count_limit: DEFAULT_DATA_PAGE_ROW_COUNT_LIMIT,
+            write_buffer_size: DEFAULT_WRITE_BUFFER_SIZE,
+        }
+    }
+}
+
+impl WriterConfig {
+    /// Build the `WriterProperties` for this config. `main` always writes
+    /// rows sorted by `time_tag_ps`, so that column is declared as the file's
+    /// sorting column regardless of config, letting downstream readers trust
+    /// and exploit the order. Page-level statistics are always enabled so the
+    /// column/offset index is populated for page-skipping reads.
+    pub(crate) fn build_properties(&self) -> WriterProperties {
+        let mut builder = WriterProperties::builder()
+            .set_compression(self.compression)
+            .set_statistics_enabled(EnabledStatistics::Page)
+            .set_data_page_row_count_limit(self.data_page_row_count_limit)
+            .set_sorting_columns(Some(vec![SortingColumn {
+                column_idx: TIME_TAG_COLUMN_IDX as i32,
+                descending: false,
+                nulls_first: false,
+            }]));
+
+        if let Some(ndv) = self.channel_bloom_filter_ndv {
+            let channel_path = ColumnPath::from(CHANNEL_COLUMN_NAME);
+            builder = builder.set_column_bloom_filter_enabled(channel_path.clone(), true);
+            if let Some(ndv) = ndv {
+                builder = builder.set_column_bloom_filter_ndv(channel_path, ndv);
+            }
+        }
+
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer::{write_time_tags, NormalizedTimeTag};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use std::fs::File;
+
+    #[test]
+    fn channel_bloom_filter_and_sorting_column_are_written() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let config = WriterConfig {
+            channel_bloom_filter_ndv: Some(Some(4)),
+            ..WriterConfig::default()
+        };
+        let time_tags = vec![
+            NormalizedTimeTag { channel_id: 0, time_tag_ps: 100 },
+            NormalizedTimeTag { channel_id: 1, time_tag_ps: 200 },
+        ];
+        write_time_tags(time_tags, output_dir.path(), "test", &config).unwrap();
+
+        let path = std::fs::read_dir(output_dir.path())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        let file = File::open(&path).unwrap();
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+        let metadata = builder.metadata();
+        let row_group = &metadata.row_groups()[0];
+        assert_eq!(
+            row_group.sorting_columns().unwrap()[0].column_idx,
+            TIME_TAG_COLUMN_IDX as i32
+        );
+
+        let channel_col_idx = metadata
+            .file_metadata()
+            .schema_descr()
+            .columns()
+            .iter()
+            .position(|col| col.name() == CHANNEL_COLUMN_NAME)
+            .unwrap();
+        assert!(
+            row_group.column(channel_col_idx).bloom_filter_offset().is_some(),
+            "expected a bloom filter on the channel column"
+        );
+    }
+}